@@ -1,15 +1,113 @@
+use std::fmt;
 use std::ops::{Add, Deref};
 
+pub mod osc;
+
 use nom::branch::alt;
-use nom::bytes::complete::{is_not, tag, take_till, take_until, take_while};
-use nom::character::complete::{anychar, char, satisfy};
+use nom::bytes::complete::{is_not, tag, take_till, take_till1, take_until, take_while};
+use nom::character::complete::char;
 use nom::combinator::all_consuming;
 use nom::multi::many1;
 use nom::sequence::{delimited, preceded, separated_pair};
-use nom::{AsChar, IResult};
+use nom::IResult;
+use regex::Regex;
+
+/// A byte offset into the original input, plus its human-readable line and
+/// column (both 1-based), mirroring the way `regex-syntax` reports error
+/// locations.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    fn new(input: &str, offset: usize) -> Self {
+        let consumed = &input[..offset.min(input.len())];
+        let line = consumed.matches('\n').count() + 1;
+        let column = match consumed.rfind('\n') {
+            Some(idx) => consumed[idx + 1..].chars().count() + 1,
+            None => consumed.chars().count() + 1,
+        };
+        Position {
+            offset,
+            line,
+            column,
+        }
+    }
+}
+
+/// The kind of problem found while parsing an OSC address or address
+/// pattern.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AddressErrorKind {
+    /// The input was empty.
+    EmptyAddress,
+    /// A `/` was followed by no content, as in `//`, a trailing `/`, or a
+    /// bare `/`.
+    EmptyPart,
+    /// A `[`/`[!` was never closed by a matching `]`.
+    UnterminatedBracket,
+    /// A `{` was never closed by a matching `}`.
+    UnterminatedAlt,
+    /// The input contained characters after the last valid part, or a part
+    /// used a character that isn't allowed in an address/pattern.
+    TrailingInput,
+}
+
+impl fmt::Display for AddressErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressErrorKind::EmptyAddress => write!(f, "address is empty"),
+            AddressErrorKind::EmptyPart => write!(f, "address part is empty"),
+            AddressErrorKind::UnterminatedBracket => write!(f, "unterminated bracket expression"),
+            AddressErrorKind::UnterminatedAlt => write!(f, "unterminated alternative"),
+            AddressErrorKind::TrailingInput => write!(f, "unexpected or trailing input"),
+        }
+    }
+}
+
+/// An error produced while parsing an OSC address or address pattern,
+/// carrying the [`Position`] of the offending part so callers can point at
+/// it in diagnostics.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AddressError {
+    kind: AddressErrorKind,
+    position: Position,
+}
+
+impl AddressError {
+    fn new(kind: AddressErrorKind, input: &str, offset: usize) -> Self {
+        AddressError {
+            kind,
+            position: Position::new(input, offset),
+        }
+    }
+
+    pub fn kind(&self) -> &AddressErrorKind {
+        &self.kind
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {} (byte {})",
+            self.kind, self.position.line, self.position.column, self.position.offset
+        )
+    }
+}
+
+impl std::error::Error for AddressError {}
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
-struct Address {
+pub struct Address {
     containers: Vec<String>,
     method: String,
 }
@@ -18,9 +116,47 @@ fn address_part(input: &str) -> IResult<&str, &str> {
     preceded(char('/'), is_not(" \t\r\n#*,/?[]{}"))(input)
 }
 
-fn parse_address(path: &str) -> IResult<&str, Address> {
+// Re-walks `input` part by part to classify why parsing it failed, since the
+// underlying nom combinators only tell us that it failed, not where or why.
+fn classify_address_error(input: &str) -> AddressError {
+    if input.is_empty() {
+        return AddressError::new(AddressErrorKind::EmptyAddress, input, 0);
+    }
+
+    let mut offset = 0;
+    let mut rest = input;
+    loop {
+        if !rest.starts_with('/') {
+            return AddressError::new(AddressErrorKind::TrailingInput, input, offset);
+        }
+        rest = &rest[1..];
+        offset += 1;
+
+        let end = rest.find('/').unwrap_or(rest.len());
+        let part = &rest[..end];
+        if part.is_empty() {
+            return AddressError::new(AddressErrorKind::EmptyPart, input, offset);
+        }
+        if let Some(bad) = part.find(|c: char| " \t\r\n#*,/?[]{}".contains(c)) {
+            return AddressError::new(AddressErrorKind::TrailingInput, input, offset + bad);
+        }
+
+        offset += part.len();
+        rest = &rest[end..];
+        if rest.is_empty() {
+            // Every part we walked was valid, so parsing would not have
+            // failed; this is only a fallback that reports the end of input.
+            return AddressError::new(AddressErrorKind::EmptyAddress, input, offset);
+        }
+    }
+}
+
+/// Parses an OSC address such as `/oscillator/4/frequency` into its
+/// containers and trailing method.
+pub fn parse_address(path: &str) -> Result<Address, AddressError> {
     // Parse at least one / preceded path part.
-    let (input, parts) = all_consuming(many1(address_part))(path)?;
+    let (_, parts) =
+        all_consuming(many1(address_part))(path).map_err(|_| classify_address_error(path))?;
 
     let containers = parts
         .iter()
@@ -33,11 +169,11 @@ fn parse_address(path: &str) -> IResult<&str, Address> {
         .expect("BUG: no OSC address method")
         .deref()
         .into();
-    Ok((input, Address { containers, method }))
+    Ok(Address { containers, method })
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum AddressPattern {
+pub enum AddressPattern {
     QuestionMark,
     Wildcard,
     BracketExpression(Vec<BracketExpression>),
@@ -47,27 +183,228 @@ enum AddressPattern {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum BracketExpression {
+pub enum BracketExpression {
     Charset(Vec<char>),
     Range { from: char, to: char },
 }
 
-fn alnum_char(input: &str) -> IResult<&str, char> {
-    satisfy(|b| b.is_alphanum())(input)
+impl BracketExpression {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            BracketExpression::Charset(chars) => chars.contains(&c),
+            BracketExpression::Range { from, to } => *from <= c && c <= *to,
+        }
+    }
 }
 
-fn alnum_range(input: &str) -> IResult<&str, BracketExpression> {
-    let (input, (from, to)) = separated_pair(alnum_char, char('-'), alnum_char)(input)?;
-    Ok((input, BracketExpression::Range { from, to }))
+// Length, in chars, that `token` consumes from the front of `remaining` if it
+// matches there. `Wildcard` is handled by the caller since it can consume a
+// variable, backtrackable amount of text.
+fn token_match_len(token: &AddressPattern, remaining: &[char]) -> Option<usize> {
+    fn starts_with(remaining: &[char], needle: &str) -> Option<usize> {
+        let needle: Vec<char> = needle.chars().collect();
+        (remaining.len() >= needle.len() && remaining[..needle.len()] == needle[..])
+            .then_some(needle.len())
+    }
+
+    match token {
+        AddressPattern::Literal(l) => starts_with(remaining, l),
+        AddressPattern::QuestionMark => (!remaining.is_empty()).then_some(1),
+        AddressPattern::BracketExpression(set) => remaining
+            .first()
+            .filter(|c| set.iter().any(|expr| expr.matches(**c)))
+            .map(|_| 1),
+        AddressPattern::InvertedBracketExpression(set) => remaining
+            .first()
+            .filter(|c| !set.iter().any(|expr| expr.matches(**c)))
+            .map(|_| 1),
+        AddressPattern::Alt(a, b) => {
+            starts_with(remaining, a).or_else(|| starts_with(remaining, b))
+        }
+        AddressPattern::Wildcard => unreachable!("Wildcard is matched by the caller"),
+    }
 }
 
-fn charset(input: &str) -> IResult<&str, BracketExpression> {
-    let (input, chars) = many1(anychar)(input)?;
-    Ok((input, BracketExpression::Charset(chars)))
+// Matches a sequence of pattern tokens (one address part, possibly a mix of
+// literals, `?`, bracket sets and `*`) against a target segment. `*` is
+// matched with backtracking: we remember the token index right after the
+// star and the text index at which we tried it, and on a later mismatch we
+// rewind there and let the star consume one more char of text.
+fn matches_segment(tokens: &[AddressPattern], segment: &str) -> bool {
+    let chars: Vec<char> = segment.chars().collect();
+    let mut pi = 0;
+    let mut ti = 0;
+    let mut star: Option<(usize, usize)> = None;
+
+    loop {
+        let matched_len = if pi < tokens.len() {
+            match &tokens[pi] {
+                AddressPattern::Wildcard => {
+                    star = Some((pi + 1, ti));
+                    pi += 1;
+                    continue;
+                }
+                token => token_match_len(token, &chars[ti..]),
+            }
+        } else {
+            None
+        };
+
+        if let Some(len) = matched_len {
+            pi += 1;
+            ti += len;
+            continue;
+        }
+
+        if pi == tokens.len() && ti == chars.len() {
+            return true;
+        }
+
+        match star {
+            Some((star_pi, star_ti)) if star_ti < chars.len() => {
+                ti = star_ti + 1;
+                pi = star_pi;
+                star = Some((star_pi, ti));
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Tests whether `address` is matched by `pattern`, part-by-part.
+///
+/// The pattern must have exactly as many parts as `address` has containers
+/// plus its trailing method, otherwise this returns `false`.
+pub fn matches(address: &Address, pattern: &[Vec<AddressPattern>]) -> bool {
+    if pattern.len() != address.containers.len() + 1 {
+        return false;
+    }
+    address
+        .containers
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(address.method.as_str()))
+        .zip(pattern)
+        .all(|(segment, part)| matches_segment(part, segment))
+}
+
+// Escapes a char for use inside a `[...]` regex character class, where `]`,
+// `^`, `-` and `\` are the only meaningful metacharacters.
+fn escape_class_char(c: char) -> String {
+    if matches!(c, ']' | '^' | '-' | '\\') {
+        format!("\\{c}")
+    } else {
+        c.to_string()
+    }
 }
 
+fn render_bracket(set: &[BracketExpression]) -> String {
+    set.iter()
+        .map(|expr| match expr {
+            // Escape the endpoints too: an unescaped leading `^` (e.g. from
+            // `[^-z]`) would otherwise be read by the regex engine as the
+            // class negation marker rather than a literal range bound.
+            BracketExpression::Range { from, to } => {
+                format!("{}-{}", escape_class_char(*from), escape_class_char(*to))
+            }
+            BracketExpression::Charset(chars) => {
+                chars.iter().copied().map(escape_class_char).collect()
+            }
+        })
+        .collect()
+}
+
+fn token_to_regex(token: &AddressPattern) -> String {
+    match token {
+        AddressPattern::Literal(l) => regex::escape(l),
+        AddressPattern::QuestionMark => "[^/]".into(),
+        AddressPattern::Wildcard => "[^/]*".into(),
+        AddressPattern::BracketExpression(set) => format!("[{}]", render_bracket(set)),
+        AddressPattern::InvertedBracketExpression(set) => format!("[^{}]", render_bracket(set)),
+        AddressPattern::Alt(a, b) => format!("(?:{}|{})", regex::escape(a), regex::escape(b)),
+    }
+}
+
+/// Lowers a parsed pattern into an anchored regular expression, suitable for
+/// matching an OSC address rendered as `container/container/.../method`.
+///
+/// Set `case_insensitive` to prefix the expression with the `(?i)` flag.
+pub fn to_regex(pattern: &[Vec<AddressPattern>], case_insensitive: bool) -> String {
+    let parts: Vec<String> = pattern
+        .iter()
+        .map(|part| part.iter().map(token_to_regex).collect())
+        .collect();
+
+    let mut re = String::from("^");
+    if case_insensitive {
+        re.push_str("(?i)");
+    }
+    re.push_str(&parts.join("/"));
+    re.push('$');
+    re
+}
+
+/// A pattern compiled to a [`Regex`], reusable across many addresses without
+/// re-walking the pattern AST on every match.
+pub struct CompiledPattern {
+    regex: Regex,
+}
+
+impl CompiledPattern {
+    pub fn new(
+        pattern: &[Vec<AddressPattern>],
+        case_insensitive: bool,
+    ) -> Result<Self, regex::Error> {
+        let regex = Regex::new(&to_regex(pattern, case_insensitive))?;
+        Ok(CompiledPattern { regex })
+    }
+
+    pub fn matches(&self, address: &Address) -> bool {
+        let path: Vec<&str> = address
+            .containers
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(address.method.as_str()))
+            .collect();
+        self.regex.is_match(&path.join("/"))
+    }
+}
+
+// Parses a bracket body (the part between `[`/`[!` and `]`) char-by-char, the
+// way regex-syntax parses class items: a `-` with a char on both sides forms
+// a `Range`, everything else accumulates into a `Charset` of loose chars. A
+// `-` that can't be the middle of a range (leading, trailing, or the first
+// char of an item, as in `[--z]`) is kept as a literal charset member; a `-`
+// right after `X-` is that range's end, so `[a--]` is rejected as inverted.
+// `[a-z0-9_]` yields two ranges and a charset.
 fn bracket(input: &str) -> IResult<&str, Vec<BracketExpression>> {
-    many1(alt((alnum_range, charset)))(input)
+    let chars: Vec<char> = input.chars().collect();
+    let mut items = Vec::new();
+    let mut charset = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '-' && i + 2 < chars.len() && chars[i + 1] == '-' {
+            let (from, to) = (chars[i], chars[i + 2]);
+            if from > to {
+                return Err(nom::Err::Failure(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                )));
+            }
+            if !charset.is_empty() {
+                items.push(BracketExpression::Charset(std::mem::take(&mut charset)));
+            }
+            items.push(BracketExpression::Range { from, to });
+            i += 3;
+            continue;
+        }
+        charset.push(chars[i]);
+        i += 1;
+    }
+    if !charset.is_empty() {
+        items.push(BracketExpression::Charset(charset));
+    }
+    Ok(("", items))
 }
 
 fn inverted_bracket_expression(input: &str) -> IResult<&str, AddressPattern> {
@@ -103,11 +440,14 @@ fn wildcard(input: &str) -> IResult<&str, AddressPattern> {
     char('*')(input).map(|(input, _)| (input, AddressPattern::Wildcard))
 }
 
+// A run of plain chars that aren't the start of another token, e.g. the
+// "osc" and "llator" in "osc*llator[0-9]".
 fn literal(input: &str) -> IResult<&str, AddressPattern> {
-    Ok(("", AddressPattern::Literal(input.into())))
+    let (input, lit) = take_till1(|c| matches!(c, '?' | '*' | '[' | '{'))(input)?;
+    Ok((input, AddressPattern::Literal(lit.into())))
 }
 
-fn parse_pattern(input: &str) -> IResult<&str, AddressPattern> {
+fn parse_token(input: &str) -> IResult<&str, AddressPattern> {
     alt((
         bracket_pattern,
         alternative,
@@ -117,13 +457,88 @@ fn parse_pattern(input: &str) -> IResult<&str, AddressPattern> {
     ))(input)
 }
 
-fn parse_address_pattern(pattern: &str) -> IResult<&str, Vec<AddressPattern>> {
-    let (input, pattern) = all_consuming(many1(preceded(char('/'), is_not(" \t\r\n/"))))(pattern)?;
-    let patterns: Result<Vec<_>, _> = pattern
+// A single address part, lowered into its sequence of tokens, e.g.
+// "osc*llator[0-9]" becomes `[Literal("osc"), Wildcard, Literal("llator"),
+// BracketExpression(...)]`.
+fn parse_pattern(input: &str) -> IResult<&str, Vec<AddressPattern>> {
+    all_consuming(many1(parse_token))(input)
+}
+
+// Tracks open/close balance rather than mere presence, so a part with
+// multiple bracket (or alt) groups where only the last one is unterminated
+// — e.g. "osc[0-9]tor[a-z" — is still caught, instead of the stray `]` from
+// the first, complete group masking the later unterminated one.
+fn has_unterminated_delimiter(part: &str, open: char, close: char) -> bool {
+    let mut depth = 0i32;
+    for c in part.chars() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+        }
+    }
+    depth > 0
+}
+
+// Mirrors `classify_address_error`, but also distinguishes unterminated
+// brackets/alternatives since those are specific to patterns.
+fn classify_pattern_error(input: &str) -> AddressError {
+    if input.is_empty() {
+        return AddressError::new(AddressErrorKind::EmptyAddress, input, 0);
+    }
+
+    let mut offset = 0;
+    let mut rest = input;
+    loop {
+        if !rest.starts_with('/') {
+            return AddressError::new(AddressErrorKind::TrailingInput, input, offset);
+        }
+        rest = &rest[1..];
+        offset += 1;
+
+        let end = rest.find('/').unwrap_or(rest.len());
+        let part = &rest[..end];
+        if part.is_empty() {
+            return AddressError::new(AddressErrorKind::EmptyPart, input, offset);
+        }
+        if let Some(bad) = part.find(|c: char| " \t\r\n".contains(c)) {
+            return AddressError::new(AddressErrorKind::TrailingInput, input, offset + bad);
+        }
+        if has_unterminated_delimiter(part, '[', ']') {
+            return AddressError::new(AddressErrorKind::UnterminatedBracket, input, offset);
+        }
+        if has_unterminated_delimiter(part, '{', '}') {
+            return AddressError::new(AddressErrorKind::UnterminatedAlt, input, offset);
+        }
+        if all_consuming(many1(parse_token))(part).is_err() {
+            return AddressError::new(AddressErrorKind::TrailingInput, input, offset);
+        }
+
+        offset += part.len();
+        rest = &rest[end..];
+        if rest.is_empty() {
+            return AddressError::new(AddressErrorKind::EmptyAddress, input, offset);
+        }
+    }
+}
+
+// Typed the same way `address_part` is, so the default `nom::error::Error<&str>`
+// applies here too instead of leaving `E` unconstrained now that the caller's
+// return type no longer pins it via `?`.
+fn pattern_part(input: &str) -> IResult<&str, &str> {
+    preceded(char('/'), is_not(" \t\r\n/"))(input)
+}
+
+/// Parses an OSC address pattern such as `/oscillator/[0-9]/frequency` into
+/// one token sequence per address part.
+pub fn parse_address_pattern(pattern: &str) -> Result<Vec<Vec<AddressPattern>>, AddressError> {
+    let (_, parts) =
+        all_consuming(many1(pattern_part))(pattern).map_err(|_| classify_pattern_error(pattern))?;
+    parts
         .iter()
-        .map(|part| parse_pattern(*part).map(|(_, pat)| pat))
-        .collect();
-    Ok((input, patterns.unwrap()))
+        .map(|part| parse_pattern(part).map(|(_, pat)| pat))
+        .collect::<Result<_, _>>()
+        .map_err(|_: nom::Err<nom::error::Error<&str>>| classify_pattern_error(pattern))
 }
 
 #[cfg(test)]
@@ -132,48 +547,161 @@ mod tests {
 
     #[test]
     fn test_parse_address_pattern() {
-        let (_, pat) =
+        let pat =
             parse_address_pattern("/oscillator/[0-9]/*/[!1234]/{frequency,phase}/x?").unwrap();
         assert_eq!(
             pat,
             vec![
-                AddressPattern::Literal("oscillator".into()),
-                AddressPattern::BracketExpression(vec![BracketExpression::Range { from: '0', to: '9' }]),
+                vec![AddressPattern::Literal("oscillator".into())],
+                vec![AddressPattern::BracketExpression(vec![
+                    BracketExpression::Range { from: '0', to: '9' }
+                ])],
+                vec![AddressPattern::Wildcard],
+                vec![AddressPattern::InvertedBracketExpression(vec![
+                    BracketExpression::Charset(vec!['1', '2', '3', '4'])
+                ])],
+                vec![AddressPattern::Alt("frequency".into(), "phase".into())],
+                vec![
+                    AddressPattern::Literal("x".into()),
+                    AddressPattern::QuestionMark
+                ]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_compound_pattern_part() {
+        let pat = parse_address_pattern("/osc*llator[0-9]/frequency").unwrap();
+        assert_eq!(
+            pat[0],
+            vec![
+                AddressPattern::Literal("osc".into()),
                 AddressPattern::Wildcard,
-                AddressPattern::InvertedBracketExpression(vec![BracketExpression::Charset(vec![
-                    '1', '2', '3', '4'
-                ])]),
-                AddressPattern::Alt("frequency".into(), "phase".into()),
-                AddressPattern::Literal("x".into()),
-                AddressPattern::QuestionMark
+                AddressPattern::Literal("llator".into()),
+                AddressPattern::BracketExpression(vec![BracketExpression::Range {
+                    from: '0',
+                    to: '9'
+                }]),
             ]
         );
     }
 
+    #[test]
+    fn test_matches_compound_pattern() {
+        let pattern = parse_address_pattern("/osc*llator[0-9]/frequency").unwrap();
+        assert!(matches(
+            &parse_address("/oscillator9/frequency").unwrap(),
+            &pattern
+        ));
+        assert!(matches(
+            &parse_address("/oscxxxxllator0/frequency").unwrap(),
+            &pattern
+        ));
+        assert!(!matches(
+            &parse_address("/oscillatorX/frequency").unwrap(),
+            &pattern
+        ));
+    }
+
+    #[test]
+    fn test_to_regex() {
+        let pattern = parse_address_pattern("/osc*llator[0-9]/{frequency,phase}").unwrap();
+        assert_eq!(
+            to_regex(&pattern, false),
+            r"^osc[^/]*llator[0-9]/(?:frequency|phase)$"
+        );
+        assert_eq!(
+            to_regex(&pattern, true),
+            r"^(?i)osc[^/]*llator[0-9]/(?:frequency|phase)$"
+        );
+    }
+
+    #[test]
+    fn test_compiled_pattern() {
+        let pattern = parse_address_pattern("/oscillator/[0-9]/frequency").unwrap();
+        let compiled = CompiledPattern::new(&pattern, false).unwrap();
+        assert!(compiled.matches(&parse_address("/oscillator/4/frequency").unwrap()));
+        assert!(!compiled.matches(&parse_address("/oscillator/x/frequency").unwrap()));
+    }
+
+    #[test]
+    fn test_mixed_bracket_expression() {
+        let pat = parse_address_pattern("/[a-z0-9_]/frequency").unwrap();
+        assert_eq!(
+            pat[0],
+            vec![AddressPattern::BracketExpression(vec![
+                BracketExpression::Range { from: 'a', to: 'z' },
+                BracketExpression::Range { from: '0', to: '9' },
+                BracketExpression::Charset(vec!['_']),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_bracket_leading_and_trailing_dash() {
+        let pat = parse_address_pattern("/[-az-]/frequency").unwrap();
+        assert_eq!(
+            pat[0],
+            vec![AddressPattern::BracketExpression(vec![
+                BracketExpression::Charset(vec!['-', 'a', 'z', '-']),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_inverted_range_is_rejected() {
+        bracket("9-0").expect_err("inverted range");
+    }
+
     #[test]
     fn test_parse_address() {
         assert_eq!(
             parse_address("/oscillator/4/voice/1/frequency"),
-            Ok((
-                "",
-                Address {
-                    containers: vec!["oscillator".into(), "4".into(), "voice".into(), "1".into()],
-                    method: "frequency".into()
-                }
-            ))
+            Ok(Address {
+                containers: vec!["oscillator".into(), "4".into(), "voice".into(), "1".into()],
+                method: "frequency".into()
+            })
         );
         assert_eq!(
             parse_address("/frequency"),
-            Ok((
-                "",
-                Address {
-                    containers: vec![],
-                    method: "frequency".into()
-                }
-            ))
+            Ok(Address {
+                containers: vec![],
+                method: "frequency".into()
+            })
         );
     }
 
+    #[test]
+    fn test_matches() {
+        let address = parse_address("/oscillator/4/frequency").unwrap();
+        let pattern = parse_address_pattern("/oscillator/[0-9]/frequency").unwrap();
+        assert!(matches(&address, &pattern));
+
+        let pattern = parse_address_pattern("/oscillator/[!0-9]/frequency").unwrap();
+        assert!(!matches(&address, &pattern));
+
+        let method_only = parse_address("/frequency").unwrap();
+        let pattern = parse_address_pattern("/frequency").unwrap();
+        assert!(matches(&method_only, &pattern));
+
+        // Mismatched part count never matches.
+        let pattern = parse_address_pattern("/oscillator/4/voice/frequency").unwrap();
+        assert!(!matches(&address, &pattern));
+    }
+
+    #[test]
+    fn test_address_error_position() {
+        let err = parse_address("//container/method").unwrap_err();
+        assert_eq!(*err.kind(), AddressErrorKind::EmptyPart);
+        assert_eq!(err.position().offset, 1);
+
+        let err = parse_address_pattern("/oscillator/[0-9/frequency").unwrap_err();
+        assert_eq!(*err.kind(), AddressErrorKind::UnterminatedBracket);
+
+        let err = parse_address("").unwrap_err();
+        assert_eq!(*err.kind(), AddressErrorKind::EmptyAddress);
+    }
+
     #[test]
     fn test_invalid_addresses() {
         parse_address("").expect_err("empty address");
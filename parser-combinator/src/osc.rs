@@ -0,0 +1,398 @@
+//! OSC message and bundle codec, built on top of the address parser: binary
+//! encode/decode against the wire format described in the OSC 1.0 spec (as
+//! implemented by e.g. the `rosc` crate).
+
+use std::fmt;
+
+use crate::{parse_address, Address, AddressError};
+
+/// An OSC argument value, tagged by its wire type-tag char (`i`, `f`, `s`,
+/// `b`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscType {
+    Int(i32),
+    Float(f32),
+    String(String),
+    Blob(Vec<u8>),
+}
+
+impl OscType {
+    fn type_tag(&self) -> char {
+        match self {
+            OscType::Int(_) => 'i',
+            OscType::Float(_) => 'f',
+            OscType::String(_) => 's',
+            OscType::Blob(_) => 'b',
+        }
+    }
+}
+
+/// An OSC message: a verified address plus its typed arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub address: Address,
+    pub args: Vec<OscType>,
+}
+
+// Renders an `Address` back to its OSC wire form, e.g. `/oscillator/4/frequency`.
+fn address_to_path(address: &Address) -> String {
+    address
+        .containers
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(address.method.as_str()))
+        .fold(String::new(), |mut path, part| {
+            path.push('/');
+            path.push_str(part);
+            path
+        })
+}
+
+/// An OSC bundle: a 64-bit NTP timetag (seconds, fraction) plus the packets
+/// it wraps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub timetag: (u32, u32),
+    pub content: Vec<Packet>,
+}
+
+/// Either an OSC message or a nested bundle, as found on the wire.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Packet {
+    Message(Message),
+    Bundle(Bundle),
+}
+
+const BUNDLE_TAG: &str = "#bundle";
+
+/// An error produced while decoding an OSC packet from bytes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OscError {
+    /// The input ended before a complete field could be read.
+    UnexpectedEof,
+    /// A string or blob's padding bytes weren't zero.
+    InvalidPadding,
+    /// An OSC-string's bytes weren't valid UTF-8.
+    InvalidUtf8,
+    /// A type-tag string didn't start with `,`.
+    MissingTypeTagComma,
+    /// A type-tag char has no corresponding argument encoding.
+    UnknownTypeTag(char),
+    /// A bundle's leading OSC-string wasn't `#bundle`.
+    NotABundle,
+    /// A blob or bundle-element size prefix was negative.
+    NegativeLength(i32),
+    /// The message address failed OSC address validation.
+    InvalidAddress(AddressError),
+}
+
+impl fmt::Display for OscError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OscError::UnexpectedEof => write!(f, "unexpected end of input"),
+            OscError::InvalidPadding => write!(f, "padding bytes are not zero"),
+            OscError::InvalidUtf8 => write!(f, "OSC-string is not valid UTF-8"),
+            OscError::MissingTypeTagComma => write!(f, "type tag string does not start with ','"),
+            OscError::UnknownTypeTag(tag) => write!(f, "unknown type tag '{tag}'"),
+            OscError::NotABundle => write!(f, "bundle does not start with '#bundle'"),
+            OscError::NegativeLength(len) => write!(f, "negative size prefix: {len}"),
+            OscError::InvalidAddress(err) => write!(f, "invalid address: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for OscError {}
+
+impl From<AddressError> for OscError {
+    fn from(err: AddressError) -> Self {
+        OscError::InvalidAddress(err)
+    }
+}
+
+// Rounds `n` up to the next multiple of 4.
+fn pad4(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+fn pad_to_4(buf: &mut Vec<u8>) {
+    let padded = pad4(buf.len());
+    buf.resize(padded, 0);
+}
+
+// Writes `s` as a null-terminated, 4-byte-aligned OSC-string.
+fn encode_osc_string(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+    pad_to_4(buf);
+}
+
+// Writes `data` as an `i32` size prefix followed by the bytes, 4-byte-aligned.
+fn encode_blob(buf: &mut Vec<u8>, data: &[u8]) {
+    buf.extend_from_slice(&(data.len() as i32).to_be_bytes());
+    buf.extend_from_slice(data);
+    pad_to_4(buf);
+}
+
+// Reads a null-terminated, 4-byte-aligned OSC-string, returning it plus the
+// unconsumed remainder of `input`.
+fn decode_osc_string(input: &[u8]) -> Result<(String, &[u8]), OscError> {
+    let nul = input
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or(OscError::UnexpectedEof)?;
+    let s = std::str::from_utf8(&input[..nul])
+        .map_err(|_| OscError::InvalidUtf8)?
+        .to_string();
+
+    let end = pad4(nul + 1);
+    if input.len() < end {
+        return Err(OscError::UnexpectedEof);
+    }
+    if input[nul..end].iter().any(|&b| b != 0) {
+        return Err(OscError::InvalidPadding);
+    }
+    Ok((s, &input[end..]))
+}
+
+// Reads an `i32`-size-prefixed, 4-byte-aligned blob, returning it plus the
+// unconsumed remainder of `input`.
+fn decode_blob(input: &[u8]) -> Result<(Vec<u8>, &[u8]), OscError> {
+    if input.len() < 4 {
+        return Err(OscError::UnexpectedEof);
+    }
+    let len = i32::from_be_bytes(input[..4].try_into().unwrap());
+    if len < 0 {
+        return Err(OscError::NegativeLength(len));
+    }
+    let len = len as usize;
+    let input = &input[4..];
+    if input.len() < len {
+        return Err(OscError::UnexpectedEof);
+    }
+
+    let end = pad4(len);
+    if input.len() < end {
+        return Err(OscError::UnexpectedEof);
+    }
+    if input[len..end].iter().any(|&b| b != 0) {
+        return Err(OscError::InvalidPadding);
+    }
+    Ok((input[..len].to_vec(), &input[end..]))
+}
+
+impl Message {
+    /// Encodes this message to its OSC wire representation: the address,
+    /// then the type-tag string, then each argument.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_osc_string(&mut buf, &address_to_path(&self.address));
+
+        let type_tags: String = std::iter::once(',')
+            .chain(self.args.iter().map(OscType::type_tag))
+            .collect();
+        encode_osc_string(&mut buf, &type_tags);
+
+        for arg in &self.args {
+            match arg {
+                OscType::Int(i) => buf.extend_from_slice(&i.to_be_bytes()),
+                OscType::Float(x) => buf.extend_from_slice(&x.to_be_bytes()),
+                OscType::String(s) => encode_osc_string(&mut buf, s),
+                OscType::Blob(data) => encode_blob(&mut buf, data),
+            }
+        }
+        buf
+    }
+
+    /// Decodes a message from its OSC wire representation, returning it
+    /// along with the unconsumed remainder of `input`. The address is
+    /// validated (and kept) via [`crate::parse_address`].
+    pub fn decode(input: &[u8]) -> Result<(Message, &[u8]), OscError> {
+        let (address, rest) = decode_osc_string(input)?;
+        let address = parse_address(&address)?;
+
+        let (type_tags, mut rest) = decode_osc_string(rest)?;
+        let mut tags = type_tags.chars();
+        if tags.next() != Some(',') {
+            return Err(OscError::MissingTypeTagComma);
+        }
+
+        let mut args = Vec::new();
+        for tag in tags {
+            let arg = match tag {
+                'i' => {
+                    if rest.len() < 4 {
+                        return Err(OscError::UnexpectedEof);
+                    }
+                    let (head, tail) = rest.split_at(4);
+                    rest = tail;
+                    OscType::Int(i32::from_be_bytes(head.try_into().unwrap()))
+                }
+                'f' => {
+                    if rest.len() < 4 {
+                        return Err(OscError::UnexpectedEof);
+                    }
+                    let (head, tail) = rest.split_at(4);
+                    rest = tail;
+                    OscType::Float(f32::from_be_bytes(head.try_into().unwrap()))
+                }
+                's' => {
+                    let (s, tail) = decode_osc_string(rest)?;
+                    rest = tail;
+                    OscType::String(s)
+                }
+                'b' => {
+                    let (data, tail) = decode_blob(rest)?;
+                    rest = tail;
+                    OscType::Blob(data)
+                }
+                other => return Err(OscError::UnknownTypeTag(other)),
+            };
+            args.push(arg);
+        }
+        Ok((Message { address, args }, rest))
+    }
+}
+
+impl Bundle {
+    /// Encodes this bundle to its OSC wire representation: the `#bundle`
+    /// OSC-string, the timetag, then each element prefixed by its size.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_osc_string(&mut buf, BUNDLE_TAG);
+        buf.extend_from_slice(&self.timetag.0.to_be_bytes());
+        buf.extend_from_slice(&self.timetag.1.to_be_bytes());
+        for packet in &self.content {
+            let encoded = packet.encode();
+            buf.extend_from_slice(&(encoded.len() as i32).to_be_bytes());
+            buf.extend_from_slice(&encoded);
+        }
+        buf
+    }
+
+    /// Decodes a bundle from its OSC wire representation, returning it along
+    /// with the unconsumed remainder of `input`.
+    pub fn decode(input: &[u8]) -> Result<(Bundle, &[u8]), OscError> {
+        let (tag, rest) = decode_osc_string(input)?;
+        if tag != BUNDLE_TAG {
+            return Err(OscError::NotABundle);
+        }
+        if rest.len() < 8 {
+            return Err(OscError::UnexpectedEof);
+        }
+        let seconds = u32::from_be_bytes(rest[..4].try_into().unwrap());
+        let fraction = u32::from_be_bytes(rest[4..8].try_into().unwrap());
+        let mut rest = &rest[8..];
+
+        let mut content = Vec::new();
+        while !rest.is_empty() {
+            if rest.len() < 4 {
+                return Err(OscError::UnexpectedEof);
+            }
+            let len = i32::from_be_bytes(rest[..4].try_into().unwrap());
+            if len < 0 {
+                return Err(OscError::NegativeLength(len));
+            }
+            let len = len as usize;
+            rest = &rest[4..];
+            if rest.len() < len {
+                return Err(OscError::UnexpectedEof);
+            }
+            let (packet, _) = Packet::decode(&rest[..len])?;
+            content.push(packet);
+            rest = &rest[len..];
+        }
+
+        Ok((
+            Bundle {
+                timetag: (seconds, fraction),
+                content,
+            },
+            rest,
+        ))
+    }
+}
+
+impl Packet {
+    /// Encodes this packet, dispatching to [`Message::encode`] or
+    /// [`Bundle::encode`].
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Packet::Message(message) => message.encode(),
+            Packet::Bundle(bundle) => bundle.encode(),
+        }
+    }
+
+    /// Decodes a packet, telling a bundle apart from a message by its
+    /// leading OSC-string.
+    pub fn decode(input: &[u8]) -> Result<(Packet, &[u8]), OscError> {
+        if input.starts_with(BUNDLE_TAG.as_bytes()) {
+            let (bundle, rest) = Bundle::decode(input)?;
+            Ok((Packet::Bundle(bundle), rest))
+        } else {
+            let (message, rest) = Message::decode(input)?;
+            Ok((Packet::Message(message), rest))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = Message {
+            address: parse_address("/oscillator/4/frequency").unwrap(),
+            args: vec![
+                OscType::Int(440),
+                OscType::Float(0.5),
+                OscType::String("sine".into()),
+                OscType::Blob(vec![1, 2, 3]),
+            ],
+        };
+        let encoded = message.encode();
+        assert_eq!(encoded.len() % 4, 0);
+        let (decoded, rest) = Message::decode(&encoded).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_message_decode_rejects_invalid_address() {
+        let mut buf = Vec::new();
+        encode_osc_string(&mut buf, "not-an-address");
+        encode_osc_string(&mut buf, ",");
+        Message::decode(&buf).expect_err("address must start with '/'");
+    }
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let inner = Message {
+            address: parse_address("/frequency").unwrap(),
+            args: vec![OscType::Int(1)],
+        };
+        let bundle = Bundle {
+            timetag: (1, 2),
+            content: vec![
+                Packet::Message(inner.clone()),
+                Packet::Bundle(Bundle {
+                    timetag: (3, 4),
+                    content: vec![Packet::Message(inner)],
+                }),
+            ],
+        };
+        let encoded = bundle.encode();
+        let (decoded, rest) = Bundle::decode(&encoded).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, bundle);
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_padding() {
+        let mut buf = Vec::new();
+        encode_osc_string(&mut buf, "/frequency");
+        encode_osc_string(&mut buf, ",");
+        *buf.last_mut().unwrap() = 1; // corrupt a padding byte
+        Message::decode(&buf).expect_err("padding bytes must be zero");
+    }
+}